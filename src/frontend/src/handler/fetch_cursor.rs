@@ -15,13 +15,13 @@
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::{Format, Row};
-use risingwave_sqlparser::ast::{FetchCursorStatement, Statement};
+use risingwave_sqlparser::ast::{FetchCursorCount, FetchCursorStatement, Statement};
 
 use super::query::handle_query;
 use super::util::gen_query_from_logstore_ge_rw_timestamp;
 use super::{HandlerArgs, RwPgResponse};
-use crate::error::{ErrorCode, Result};
-use crate::session::cursor_manager::CursorRowValue;
+use crate::error::Result;
+use crate::session::cursor_manager::CursorManager;
 use crate::{Binder, PgResponseStream};
 
 pub async fn handle_fetch_cursor(
@@ -34,60 +34,43 @@ pub async fn handle_fetch_cursor(
     let (schema_name, cursor_name) =
         Binder::resolve_schema_qualified_name(db_name, stmt.cursor_name.clone())?;
 
-    let cursor_manager = session.get_cursor_manager();
-    let mut cursor_manager = cursor_manager.lock().await;
-    // Fetch data from the Cursor. There are three cases
-    let (rw_timestamp, subscription_name, need_check_timestamp) = match cursor_manager
-        .get_row_with_cursor(cursor_name.clone())
-        .await?
-    {
-        CursorRowValue::Row((row, pg_descs)) => {
-            // Normal row
-            return Ok(build_fetch_cursor_response(vec![row], pg_descs));
-        }
-        CursorRowValue::QueryWithNextRwTimestamp(rw_timestamp, subscription_name) => {
-            // Returned the rw_timestamp of the next cursor, query data and update the cursor
-            (rw_timestamp, subscription_name, true)
-        }
-        CursorRowValue::QueryWithStartRwTimestamp(rw_timestamp, subscription_name) => {
-            // The rw_timestamp for the next cursor has not been returned, and +1 query it.
-            (rw_timestamp + 1, subscription_name, false)
-        }
+    // `FETCH <count>` / `FETCH FORWARD <count>` batches up to `count` rows into one response;
+    // `FETCH ALL` drains the cursor.
+    let requested_count = match stmt.count {
+        FetchCursorCount::Count(count) => Some(count.max(0) as usize),
+        FetchCursorCount::All => None,
     };
-    let subscription = session.get_subscription_by_name(
-        schema_name,
-        &subscription_name.0.last().unwrap().real_value().clone(),
-    )?;
-    let query_stmt = Statement::Query(Box::new(gen_query_from_logstore_ge_rw_timestamp(
-        &subscription.get_log_store_name()?,
-        rw_timestamp,
-    )));
-    let res = handle_query(handle_args, query_stmt, formats).await?;
-    cursor_manager
-        .update_cursor(
-            cursor_name.clone(),
-            res,
-            rw_timestamp,
-            false,
-            need_check_timestamp,
-            subscription_name.clone(),
-            subscription.get_retention_seconds()?,
-        )
-        .await?;
 
-    // Try fetch data after update cursor
-    match cursor_manager.get_row_with_cursor(cursor_name).await? {
-        CursorRowValue::Row((row, pg_descs)) => {
-            Ok(build_fetch_cursor_response(vec![row], pg_descs))
-        }
-        CursorRowValue::QueryWithStartRwTimestamp(_, _) => {
-            Ok(build_fetch_cursor_response(vec![], vec![]))
-        }
-        CursorRowValue::QueryWithNextRwTimestamp(_, _) => Err(ErrorCode::InternalError(
-            "Fetch cursor, one must get a row or null".to_string(),
-        )
-        .into()),
-    }
+    let cursor_manager = session.get_cursor_manager();
+    // Passed by reference rather than locked up front: `fetch_rows_with_resume` re-acquires the
+    // lock around each of its internal steps so that a multi-retry `FETCH` (sleeping and
+    // re-querying between empty polls) doesn't hold the lock and block unrelated cursor
+    // operations on this session for the whole retry sequence.
+    let (rows, pg_descs) = CursorManager::fetch_rows_with_resume(
+        &cursor_manager,
+        cursor_name,
+        requested_count,
+        |rw_timestamp, subscription_name| {
+            let handle_args = handle_args.clone();
+            let formats = formats.clone();
+            let session = session.clone();
+            let schema_name = schema_name.clone();
+            async move {
+                let subscription = session.get_subscription_by_name(
+                    schema_name,
+                    &subscription_name.0.last().unwrap().real_value().clone(),
+                )?;
+                let query_stmt = Statement::Query(Box::new(gen_query_from_logstore_ge_rw_timestamp(
+                    &subscription.get_log_store_name()?,
+                    rw_timestamp,
+                )));
+                let res = handle_query(handle_args, query_stmt, formats).await?;
+                Ok((res, subscription.get_retention_seconds()?))
+            }
+        },
+    )
+    .await?;
+    Ok(build_fetch_cursor_response(rows, pg_descs))
 }
 
 fn build_fetch_cursor_response(rows: Vec<Row>, pg_descs: Vec<PgFieldDescriptor>) -> RwPgResponse {
@@ -15,6 +15,7 @@
 use core::ops::Index;
 use core::time::Duration;
 use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::time::Instant;
 
 use bytes::Bytes;
@@ -95,9 +96,21 @@ impl Cursor {
         })
     }
 
-    pub async fn next(&mut self) -> Result<CursorRowValue> {
+    /// Returns up to `limit` rows (unbounded, i.e. `FETCH ALL`, when `limit` is `None`) that are
+    /// already buffered or can be drained from the underlying stream without issuing a new
+    /// log-store query. Stops early and returns whatever was accumulated so far as soon as a row
+    /// belonging to a later `rw_timestamp` is *peeked*, leaving that row in place so the next
+    /// call observes the boundary and returns `QueryWithNextRwTimestamp`, exactly as the
+    /// single-row path always has.
+    pub async fn next(&mut self, limit: Option<usize>) -> Result<CursorRowValue> {
         let stream = self.rw_pg_response.values_stream();
+        let mut rows = Vec::new();
         loop {
+            if let Some(limit) = limit
+                && rows.len() >= limit
+            {
+                break;
+            }
             if self.data_chunk_cache.is_empty() {
                 // 1. Cache is empty, need to query data
                 if let Some(row_set) = stream.next().await {
@@ -108,47 +121,61 @@ impl Cursor {
                             e.to_string()
                         ))
                     })?);
-                } else {
+                    if self.data_chunk_cache.is_empty() {
+                        // The stream yielded a batch that decoded to zero rows; loop back and
+                        // poll it again instead of falling through to index into an empty cache.
+                        continue;
+                    }
+                } else if rows.is_empty() {
                     // 1b. No data was fetched and next_rw_timestamp was not found, so need to query using the rw_timestamp+1.
                     return Ok(CursorRowValue::QueryWithStartRwTimestamp(
                         self.rw_timestamp,
                         self.subscription_name.clone(),
                     ));
+                } else {
+                    // 1c. The log store is drained for now; return what this batch already has.
+                    break;
                 }
             }
-            if let Some(row) = self.data_chunk_cache.pop_front() {
-                // 2. fetch data
-                let new_row = row.take();
-                if self.is_snapshot {
-                    // 2a. The rw_timestamp in the table is all the same, so don't need to check.
-                    return Ok(CursorRowValue::Row((
-                        Row::new(build_row_with_snapshot(new_row, self.rw_timestamp)),
-                        self.pg_desc.clone(),
-                    )));
-                }
-
-                let timestamp_row: i64 = new_row
-                    .get(0)
+            if !self.is_snapshot {
+                // Peek (without consuming) so a timestamp boundary found after the first row
+                // doesn't silently drop the row that belongs to the next batch.
+                let timestamp_row: i64 = self
+                    .data_chunk_cache
+                    .front()
                     .unwrap()
+                    .index(0)
                     .as_ref()
                     .map(|bytes| std::str::from_utf8(bytes).unwrap().parse().unwrap())
                     .unwrap();
-
                 if timestamp_row != self.rw_timestamp {
-                    // 2b. Find next_rw_timestamp, need update cursor with next_rw_timestamp.
-                    return Ok(CursorRowValue::QueryWithNextRwTimestamp(
-                        timestamp_row,
-                        self.subscription_name.clone(),
-                    ));
-                } else {
-                    // 2c. The rw_timestamp of this row is equal to self.rw_timestamp, return row
-                    return Ok(CursorRowValue::Row((
-                        Row::new(build_row_with_logstore(new_row, timestamp_row)?),
-                        self.pg_desc.clone(),
-                    )));
+                    if rows.is_empty() {
+                        // 2a. Find next_rw_timestamp, need update cursor with next_rw_timestamp.
+                        self.data_chunk_cache.pop_front();
+                        return Ok(CursorRowValue::QueryWithNextRwTimestamp(
+                            timestamp_row,
+                            self.subscription_name.clone(),
+                        ));
+                    } else {
+                        break;
+                    }
                 }
             }
+            // 2b. fetch data
+            let row = self.data_chunk_cache.pop_front().unwrap();
+            let new_row = row.take();
+            if self.is_snapshot {
+                // The rw_timestamp in the table is all the same, so don't need to check.
+                rows.push(Row::new(build_row_with_snapshot(new_row, self.rw_timestamp)));
+            } else {
+                // The rw_timestamp of this row is equal to self.rw_timestamp, return row
+                rows.push(Row::new(build_row_with_logstore(
+                    new_row,
+                    self.rw_timestamp,
+                )?));
+            }
         }
+        Ok(CursorRowValue::Rows(rows, self.pg_desc.clone()))
     }
 }
 
@@ -197,7 +224,9 @@ pub fn build_desc(mut descs: Vec<PgFieldDescriptor>, is_snapshot: bool) -> Vec<P
 }
 
 pub enum CursorRowValue {
-    Row((Row, Vec<PgFieldDescriptor>)),
+    /// Up to the requested batch count of already-buffered rows (empty only when a batch count
+    /// of 0 is requested).
+    Rows(Vec<Row>, Vec<PgFieldDescriptor>),
     QueryWithNextRwTimestamp(i64, ObjectName),
     QueryWithStartRwTimestamp(i64, ObjectName),
 }
@@ -209,6 +238,20 @@ pub struct CursorManager {
 }
 
 impl CursorManager {
+    /// Initial backoff between consecutive empty log-store polls in
+    /// [`Self::fetch_rows_with_resume`], doubled on each retry up to
+    /// [`Self::FETCH_RESUME_MAX_BACKOFF`].
+    const FETCH_RESUME_INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+    const FETCH_RESUME_MAX_BACKOFF: Duration = Duration::from_secs(1);
+    /// Number of consecutive empty polls [`Self::fetch_rows_with_resume`] tolerates before giving
+    /// up and returning whatever rows were accumulated so far.
+    const FETCH_RESUME_MAX_RETRIES: u32 = 5;
+
+    /// Doubles `current`, capped at [`Self::FETCH_RESUME_MAX_BACKOFF`].
+    fn next_backoff(current: Duration) -> Duration {
+        (current * 2).min(Self::FETCH_RESUME_MAX_BACKOFF)
+    }
+
     pub fn add_cursor_retention_secs(
         &mut self,
         subscription_name: ObjectName,
@@ -234,7 +277,30 @@ impl CursorManager {
         Ok(())
     }
 
-    pub fn update_cursor(&mut self, cursor: Cursor) -> Result<()> {
+    #[expect(clippy::too_many_arguments)]
+    pub async fn update_cursor(
+        &mut self,
+        cursor_name: String,
+        rw_pg_response: RwPgResponse,
+        start_timestamp: i64,
+        is_snapshot: bool,
+        need_check_timestamp: bool,
+        subscription_name: ObjectName,
+        retention_seconds: u64,
+    ) -> Result<()> {
+        self.add_cursor_retention_secs(
+            subscription_name.clone(),
+            Duration::from_secs(retention_seconds),
+        );
+        let cursor = Cursor::new(
+            cursor_name,
+            rw_pg_response,
+            start_timestamp,
+            is_snapshot,
+            need_check_timestamp,
+            subscription_name,
+        )
+        .await?;
         let cursor_need_drop_time = Instant::now()
             + *self
                 .cursor_retention_secs_maps
@@ -255,7 +321,14 @@ impl CursorManager {
         Ok(())
     }
 
-    pub async fn get_row_with_cursor(&mut self, cursor_name: String) -> Result<CursorRowValue> {
+    /// Returns up to `count` buffered rows for `cursor_name` (`None` for `FETCH ALL`). See
+    /// [`Cursor::next`] for how a batch can fall short of `count` without the cursor being
+    /// drained yet.
+    pub async fn get_row_with_cursor(
+        &mut self,
+        cursor_name: String,
+        count: Option<usize>,
+    ) -> Result<CursorRowValue> {
         if let Some((cursor, cursor_need_drop_time)) = self.cursor_map.get_mut(&cursor_name) {
             if Instant::now() > *cursor_need_drop_time {
                 self.remove_cursor(cursor_name)?;
@@ -264,9 +337,134 @@ impl CursorManager {
                 )
                 .into());
             }
-            cursor.next().await
+            cursor.next(count).await
         } else {
             Err(ErrorCode::ItemNotFound(format!("Don't find cursor `{}`", cursor_name)).into())
         }
     }
+
+    /// Drives a subscription cursor to completion as a single awaitable, hiding the
+    /// `QueryWithNextRwTimestamp`/`QueryWithStartRwTimestamp` state machine from the caller.
+    ///
+    /// `requery(rw_timestamp, subscription_name)` is called to re-run the subscription log-store
+    /// query starting at `rw_timestamp` and should return the resulting [`RwPgResponse`] plus the
+    /// subscription's retention in seconds; this lets the handler own query execution (which needs
+    /// the session/binder context) while the cursor owns the resumption logic.
+    ///
+    /// Returns up to `count` rows (`None` for `FETCH ALL`), resuming across as many timestamp
+    /// boundaries as it takes to satisfy `count` or exhaust [`Self::FETCH_RESUME_MAX_RETRIES`]
+    /// consecutive empty polls, backing off exponentially between those polls so a `FETCH` on a
+    /// cursor with no new data yet doesn't hot-spin the log store.
+    ///
+    /// Takes `cursor_manager` rather than `&mut self` and re-acquires the lock around each
+    /// individual `get_row_with_cursor`/`update_cursor` call instead of holding it for the whole
+    /// method: a `FETCH` that needs several empty-poll retries would otherwise keep the lock (and
+    /// sleep inside it) for as long as the full backoff/requery sequence, serializing every other
+    /// cursor operation on the session for that long.
+    pub async fn fetch_rows_with_resume<F, Fut>(
+        cursor_manager: &tokio::sync::Mutex<Self>,
+        cursor_name: String,
+        count: Option<usize>,
+        mut requery: F,
+    ) -> Result<(Vec<Row>, Vec<PgFieldDescriptor>)>
+    where
+        F: FnMut(i64, ObjectName) -> Fut,
+        Fut: Future<Output = Result<(RwPgResponse, u64)>>,
+    {
+        let mut rows = Vec::new();
+        let mut pg_descs = Vec::new();
+        let mut backoff = Self::FETCH_RESUME_INITIAL_BACKOFF;
+        let mut empty_retries = 0u32;
+        while count.is_none_or(|count| rows.len() < count) {
+            let remaining = count.map(|count| count - rows.len());
+            let row_value = cursor_manager
+                .lock()
+                .await
+                .get_row_with_cursor(cursor_name.clone(), remaining)
+                .await?;
+            match row_value {
+                CursorRowValue::Rows(batch, descs) => {
+                    if !descs.is_empty() {
+                        pg_descs = descs;
+                    }
+                    if !batch.is_empty() {
+                        empty_retries = 0;
+                        backoff = Self::FETCH_RESUME_INITIAL_BACKOFF;
+                    }
+                    rows.extend(batch);
+                }
+                CursorRowValue::QueryWithNextRwTimestamp(rw_timestamp, subscription_name) => {
+                    let (rw_pg_response, retention_seconds) =
+                        requery(rw_timestamp, subscription_name.clone()).await?;
+                    cursor_manager
+                        .lock()
+                        .await
+                        .update_cursor(
+                            cursor_name.clone(),
+                            rw_pg_response,
+                            rw_timestamp,
+                            false,
+                            true,
+                            subscription_name,
+                            retention_seconds,
+                        )
+                        .await?;
+                    empty_retries = 0;
+                    backoff = Self::FETCH_RESUME_INITIAL_BACKOFF;
+                }
+                CursorRowValue::QueryWithStartRwTimestamp(rw_timestamp, subscription_name) => {
+                    if empty_retries >= Self::FETCH_RESUME_MAX_RETRIES {
+                        // The log store has come up empty on every retry; stop here and return
+                        // whatever was accumulated rather than spinning forever.
+                        break;
+                    }
+                    if empty_retries > 0 {
+                        tokio::time::sleep(backoff).await;
+                        backoff = Self::next_backoff(backoff);
+                    }
+                    let rw_timestamp = rw_timestamp + 1;
+                    let (rw_pg_response, retention_seconds) =
+                        requery(rw_timestamp, subscription_name.clone()).await?;
+                    cursor_manager
+                        .lock()
+                        .await
+                        .update_cursor(
+                            cursor_name.clone(),
+                            rw_pg_response,
+                            rw_timestamp,
+                            false,
+                            false,
+                            subscription_name,
+                            retention_seconds,
+                        )
+                        .await?;
+                    empty_retries += 1;
+                }
+            }
+        }
+        Ok((rows, pg_descs))
+    }
+}
+
+// `fetch_rows_with_resume`'s retry/backoff state machine otherwise has no test coverage in this
+// checkout: exercising it end-to-end needs a live `Cursor`/`RwPgResponse`/session, none of which
+// this sparse checkout's `src/frontend` has fixtures for. `next_backoff` is the one piece of that
+// state machine that's pure and free-standing, so it's what gets covered here.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_backoff_doubles_up_to_cap() {
+        let mut backoff = CursorManager::FETCH_RESUME_INITIAL_BACKOFF;
+        assert_eq!(backoff, Duration::from_millis(50));
+        backoff = CursorManager::next_backoff(backoff);
+        assert_eq!(backoff, Duration::from_millis(100));
+        backoff = CursorManager::next_backoff(backoff);
+        assert_eq!(backoff, Duration::from_millis(200));
+        for _ in 0..10 {
+            backoff = CursorManager::next_backoff(backoff);
+        }
+        assert_eq!(backoff, CursorManager::FETCH_RESUME_MAX_BACKOFF);
+    }
 }
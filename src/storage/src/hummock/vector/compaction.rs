@@ -0,0 +1,176 @@
+// Copyright 2025 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compaction of HNSW vector-index segments, analogous to SST compaction: many small
+//! [`VectorFileInfo`]s and an ever-growing graph are merged into a single compacted
+//! generation so that file fan-out and graph-rebuild cost stay bounded over time.
+
+use bytes::BytesMut;
+use prost::Message;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use risingwave_hummock_sdk::HummockObjectId;
+use risingwave_hummock_sdk::vector_index::{
+    HnswFlatIndexCompact, HnswGraphFileInfo, VectorFileInfo, VectorIndexAdd,
+};
+use risingwave_pb::hummock::PbHnswGraph;
+use roaring::RoaringBitmap;
+
+use crate::dispatch_measurement;
+use crate::hummock::vector::file::VectorFileBuilder;
+use crate::hummock::vector::metrics::VectorIndexMetrics;
+use crate::hummock::vector::writer::new_vector_file_builder;
+use crate::hummock::vector::{EnumVectorAccessor, get_vector_block};
+use crate::hummock::{HummockResult, ObjectIdManagerRef, SstableStoreRef};
+use crate::opts::StorageOpts;
+use crate::vector::DistanceMeasurement;
+use crate::vector::hnsw::{
+    HnswBuilderOptions, HnswGraphBuilder, VectorAccessor, VectorStore, insert_graph, new_node,
+};
+
+/// Policy deciding when a segment's vector files and graph should be compacted. Mirrors the
+/// file-count/bytes/garbage-ratio triggers used to schedule SST compaction.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CompactionTriggerPolicy {
+    pub(crate) max_vector_file_count: usize,
+    pub(crate) max_total_vector_file_bytes: u64,
+    pub(crate) deleted_ratio: f64,
+}
+
+impl Default for CompactionTriggerPolicy {
+    // TODO(follow-up): surface these as `vector_index_compaction_max_file_count` /
+    // `max_file_bytes` / `deleted_ratio` knobs on `StorageOpts` instead of compiled-in defaults.
+    fn default() -> Self {
+        Self {
+            max_vector_file_count: 32,
+            max_total_vector_file_bytes: 256 * 1024 * 1024,
+            deleted_ratio: 0.2,
+        }
+    }
+}
+
+impl CompactionTriggerPolicy {
+    /// Returns whether `vector_files`/`deleted` have crossed any of the configured triggers.
+    pub(crate) fn should_compact(
+        &self,
+        vector_files: &[VectorFileInfo],
+        deleted: &RoaringBitmap,
+        total_vectors: usize,
+    ) -> bool {
+        if vector_files.len() >= self.max_vector_file_count {
+            return true;
+        }
+        let total_bytes: u64 = vector_files.iter().map(|file| file.file_size).sum();
+        if total_bytes >= self.max_total_vector_file_bytes {
+            return true;
+        }
+        total_vectors > 0 && deleted.len() as f64 / total_vectors as f64 >= self.deleted_ratio
+    }
+}
+
+/// A [`VectorStore`] over the vectors streamed into a [`VectorFileBuilder`] mid-compaction, so
+/// `insert_graph` can route through the partially rebuilt graph the same way it would through a
+/// committed `HnswVectorStore`.
+struct BuildingVectorStore<'a>(&'a VectorFileBuilder);
+
+impl VectorStore for BuildingVectorStore<'_> {
+    type Accessor<'b>
+        = EnumVectorAccessor<'b>
+    where
+        Self: 'b;
+
+    async fn get_vector(&self, idx: usize) -> HummockResult<Self::Accessor<'_>> {
+        self.0.get_vector(idx)
+    }
+}
+
+/// Streams every live (non-tombstoned) vector out of `vector_files`, reassigns contiguous vector
+/// ids starting at 0, rebuilds a single HNSW graph over the remapped ids, and returns a
+/// [`VectorIndexAdd`] that atomically swaps the compacted files/graph in for the originals.
+pub(crate) async fn compact_hnsw_segment(
+    measure: &DistanceMeasurement,
+    options: &HnswBuilderOptions,
+    dimension: usize,
+    total_vectors: usize,
+    vector_files: &[VectorFileInfo],
+    deleted: &RoaringBitmap,
+    sstable_store: SstableStoreRef,
+    object_id_manager: ObjectIdManagerRef,
+    storage_opts: &StorageOpts,
+    metrics: VectorIndexMetrics,
+    index_id: String,
+) -> HummockResult<VectorIndexAdd> {
+    let mut building_vectors = new_vector_file_builder(
+        dimension,
+        0,
+        sstable_store.clone(),
+        object_id_manager.clone(),
+        storage_opts,
+    );
+    let mut graph_builder: Option<HnswGraphBuilder> = None;
+    let mut rng = StdRng::from_os_rng();
+
+    for old_id in 0..total_vectors {
+        if deleted.contains(old_id as u32) {
+            continue;
+        }
+        let accessor =
+            get_vector_block(&sstable_store, vector_files, old_id, &metrics, &index_id).await?;
+        let info = accessor.info().to_vec();
+        building_vectors.add(accessor.vec_ref(), &info);
+
+        let node = new_node(options, &mut rng);
+        if let Some(graph_builder) = &mut graph_builder {
+            let vector_store = BuildingVectorStore(&building_vectors);
+            dispatch_measurement!(measure, M, {
+                insert_graph::<M>(
+                    &vector_store,
+                    graph_builder,
+                    node,
+                    accessor.vec_ref(),
+                    options.ef_construction,
+                )
+                .await?;
+            });
+        } else {
+            graph_builder = Some(HnswGraphBuilder::first(node));
+        }
+    }
+
+    let mut added_vector_files = vec![];
+    if let Some((vector_file, blocks, meta)) = building_vectors.finish().await? {
+        sstable_store.insert_vector_cache(vector_file.object_id, meta, blocks);
+        added_vector_files.push(vector_file);
+    }
+
+    let graph_builder =
+        graph_builder.expect("segment marked eligible for compaction must be non-empty");
+    let pb_graph = graph_builder.to_protobuf();
+    let mut buffer = BytesMut::with_capacity(pb_graph.encoded_len());
+    PbHnswGraph::encode(&pb_graph, &mut buffer).unwrap();
+    let encoded_graph = buffer.freeze();
+    let graph_size = encoded_graph.len();
+    let object_id = object_id_manager.get_new_object_id().await?;
+    let path = sstable_store.get_object_data_path(HummockObjectId::HnswGraphFile(object_id));
+    sstable_store.store().upload(&path, encoded_graph).await?;
+
+    Ok(VectorIndexAdd::HnswFlatCompact(HnswFlatIndexCompact {
+        removed_vector_files: vector_files.to_vec(),
+        added_vector_files,
+        graph_file: HnswGraphFileInfo {
+            object_id,
+            file_size: graph_size as _,
+        },
+    }))
+}
@@ -0,0 +1,146 @@
+// Copyright 2025 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Observability for the vector index writer and vector block cache: insert throughput, HNSW
+//! build cost, bytes flushed, and cache hit rate, so operators can see build backlog and cache
+//! efficiency per index.
+
+use prometheus::core::{AtomicU64, GenericCounter, GenericCounterVec};
+use prometheus::{
+    Histogram, HistogramVec, Registry, exponential_buckets, register_histogram_vec_with_registry,
+    register_int_counter_vec_with_registry,
+};
+
+#[derive(Clone)]
+pub(crate) struct VectorIndexMetrics {
+    /// Number of vectors inserted into `HnswFlatIndexWriter::insert`, by index id.
+    pub(crate) insert_count: GenericCounterVec<AtomicU64>,
+    /// Latency of a single `add_pending_vectors_to_graph` node insertion, by index id.
+    pub(crate) graph_insert_latency: HistogramVec,
+    /// Number of distance computations performed while walking `ef_construction` candidates.
+    pub(crate) ef_construction_distance_count: GenericCounterVec<AtomicU64>,
+    /// Bytes written per `HnswFlatIndexWriter::flush` call, by index id.
+    pub(crate) flush_bytes: HistogramVec,
+    /// Size in bytes of the encoded `PbHnswGraph` produced by each flush.
+    pub(crate) encoded_graph_size: HistogramVec,
+    /// Number of vector files in the set that `search_vector_files` binary-searches, per lookup.
+    pub(crate) vector_files_traversed: HistogramVec,
+    /// Vector block cache hits/misses, by index id and `hit`/`miss`.
+    pub(crate) block_cache_access: GenericCounterVec<AtomicU64>,
+}
+
+impl VectorIndexMetrics {
+    pub(crate) fn new(registry: &Registry) -> Self {
+        let insert_count = register_int_counter_vec_with_registry!(
+            "vector_index_insert_count",
+            "number of vectors inserted into the HNSW vector index writer",
+            &["index_id"],
+            registry
+        )
+        .unwrap();
+        let graph_insert_latency = register_histogram_vec_with_registry!(
+            "vector_index_graph_insert_latency",
+            "latency of inserting one pending vector into the HNSW graph",
+            &["index_id"],
+            registry
+        )
+        .unwrap();
+        let ef_construction_distance_count = register_int_counter_vec_with_registry!(
+            "vector_index_ef_construction_distance_count",
+            "number of distance computations performed during ef_construction candidate search",
+            &["index_id"],
+            registry
+        )
+        .unwrap();
+        let flush_bytes = register_histogram_vec_with_registry!(
+            "vector_index_flush_bytes",
+            "bytes flushed per HnswFlatIndexWriter::flush call",
+            &["index_id"],
+            exponential_buckets(1024.0, 2.0, 20).unwrap(),
+            registry
+        )
+        .unwrap();
+        let encoded_graph_size = register_histogram_vec_with_registry!(
+            "vector_index_encoded_graph_size",
+            "size in bytes of the encoded HNSW graph after a flush",
+            &["index_id"],
+            exponential_buckets(1024.0, 2.0, 20).unwrap(),
+            registry
+        )
+        .unwrap();
+        let vector_files_traversed = register_histogram_vec_with_registry!(
+            "vector_index_vector_files_traversed",
+            "number of vector files searched over by search_vector_files per lookup",
+            &["index_id"],
+            registry
+        )
+        .unwrap();
+        let block_cache_access = register_int_counter_vec_with_registry!(
+            "vector_index_block_cache_access",
+            "vector block cache hits/misses",
+            &["index_id", "result"],
+            registry
+        )
+        .unwrap();
+        Self {
+            insert_count,
+            graph_insert_latency,
+            ef_construction_distance_count,
+            flush_bytes,
+            encoded_graph_size,
+            vector_files_traversed,
+            block_cache_access,
+        }
+    }
+
+    pub(crate) fn insert_counter(&self, index_id: &str) -> GenericCounter<AtomicU64> {
+        self.insert_count.with_label_values(&[index_id])
+    }
+
+    pub(crate) fn graph_insert_timer(&self, index_id: &str) -> prometheus::HistogramTimer {
+        self.graph_insert_latency
+            .with_label_values(&[index_id])
+            .start_timer()
+    }
+
+    pub(crate) fn record_ef_construction_distance_count(&self, index_id: &str, count: u64) {
+        self.ef_construction_distance_count
+            .with_label_values(&[index_id])
+            .inc_by(count);
+    }
+
+    pub(crate) fn flush_bytes_histogram(&self, index_id: &str) -> Histogram {
+        self.flush_bytes.with_label_values(&[index_id])
+    }
+
+    pub(crate) fn encoded_graph_size_histogram(&self, index_id: &str) -> Histogram {
+        self.encoded_graph_size.with_label_values(&[index_id])
+    }
+
+    pub(crate) fn vector_files_traversed_histogram(&self, index_id: &str) -> Histogram {
+        self.vector_files_traversed.with_label_values(&[index_id])
+    }
+
+    pub(crate) fn record_block_cache_hit(&self, index_id: &str) {
+        self.block_cache_access
+            .with_label_values(&[index_id, "hit"])
+            .inc();
+    }
+
+    pub(crate) fn record_block_cache_miss(&self, index_id: &str) {
+        self.block_cache_access
+            .with_label_values(&[index_id, "miss"])
+            .inc();
+    }
+}
@@ -15,11 +15,14 @@
 use risingwave_hummock_sdk::vector_index::VectorFileInfo;
 
 use crate::hummock::vector::file::{VectorBlock, VectorBlockBuilder, VectorBlockMeta};
+use crate::hummock::vector::metrics::VectorIndexMetrics;
 use crate::hummock::{HummockError, HummockResult, SstableStoreRef, VectorBlockHolder};
 use crate::vector::VectorRef;
 use crate::vector::hnsw::{VectorAccessor, VectorStore};
 
+pub(crate) mod compaction;
 pub(crate) mod file;
+pub(crate) mod metrics;
 pub(crate) mod writer;
 
 pub struct VectorBlockAccessor {
@@ -61,30 +64,54 @@ impl VectorAccessor for EnumVectorAccessor<'_> {
     }
 }
 
+// `metrics`/`index_id` are new params; any caller outside this checkout's query path must be
+// updated to pass them through.
 pub async fn get_vector_block(
     sstable_store: &SstableStoreRef,
     files: &[VectorFileInfo],
     idx: usize,
+    metrics: &VectorIndexMetrics,
+    index_id: &str,
 ) -> HummockResult<VectorBlockAccessor> {
+    metrics
+        .vector_files_traversed_histogram(index_id)
+        .observe(files.len() as f64);
     let vector_file = search_vector_files(files, idx)?;
     let meta = sstable_store.get_vector_file_meta(vector_file).await?;
     let (block_meta, block_idx, offset) = search_blocks(&meta.block_metas, idx)?;
+    let cached = sstable_store.is_vector_block_cached(vector_file, block_idx);
     let block = sstable_store
         .get_vector_block(vector_file, block_idx, block_meta)
         .await?;
+    if cached {
+        metrics.record_block_cache_hit(index_id);
+    } else {
+        metrics.record_block_cache_miss(index_id);
+    }
     Ok(VectorBlockAccessor { block, idx: offset })
 }
 
 pub struct FileVectorStore {
     vector_files: Vec<VectorFileInfo>,
     sstable_store: SstableStoreRef,
+    metrics: VectorIndexMetrics,
+    index_id: String,
 }
 
 impl FileVectorStore {
-    pub fn new(vector_files: Vec<VectorFileInfo>, sstable_store: SstableStoreRef) -> Self {
+    // `metrics`/`index_id` are new params; any caller outside this checkout's query path must be
+    // updated to pass them through.
+    pub fn new(
+        vector_files: Vec<VectorFileInfo>,
+        sstable_store: SstableStoreRef,
+        metrics: VectorIndexMetrics,
+        index_id: String,
+    ) -> Self {
         Self {
             vector_files,
             sstable_store,
+            metrics,
+            index_id,
         }
     }
 }
@@ -96,7 +123,14 @@ impl VectorStore for FileVectorStore {
         Self: 'a;
 
     async fn get_vector(&self, idx: usize) -> HummockResult<Self::Accessor<'_>> {
-        get_vector_block(&self.sstable_store, &self.vector_files, idx).await
+        get_vector_block(
+            &self.sstable_store,
+            &self.vector_files,
+            idx,
+            &self.metrics,
+            &self.index_id,
+        )
+        .await
     }
 }
 
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::Cell;
 use std::mem::take;
 
 use bytes::{Bytes, BytesMut};
@@ -24,21 +25,26 @@ use risingwave_hummock_sdk::vector_index::{
     VectorStoreInfoDelta,
 };
 use risingwave_pb::hummock::PbHnswGraph;
+use roaring::RoaringBitmap;
 
 use crate::dispatch_measurement;
+use crate::hummock::vector::compaction::{CompactionTriggerPolicy, compact_hnsw_segment};
 use crate::hummock::vector::file::VectorFileBuilder;
+use crate::hummock::vector::metrics::VectorIndexMetrics;
 use crate::hummock::vector::writer::new_vector_file_builder;
 use crate::hummock::vector::{EnumVectorAccessor, get_vector_block};
 use crate::hummock::{HummockError, HummockResult, ObjectIdManagerRef, SstableStoreRef};
 use crate::opts::StorageOpts;
 use crate::store::Vector;
-use crate::vector::DistanceMeasurement;
+use crate::vector::{DistanceMeasurement, VectorDistance};
 use crate::vector::hnsw::{
     HnswBuilderOptions, HnswGraphBuilder, VectorAccessor, VectorStore, insert_graph, new_node,
 };
 
 struct HnswVectorStore {
     sstable_store: SstableStoreRef,
+    metrics: VectorIndexMetrics,
+    index_id: String,
 
     committed_vector_files: Vec<VectorFileInfo>,
     committed_next_vector_id: usize,
@@ -56,10 +62,14 @@ impl HnswVectorStore {
         sstable_store: SstableStoreRef,
         object_id_manager: ObjectIdManagerRef,
         storage_opts: &StorageOpts,
+        metrics: VectorIndexMetrics,
+        index_id: String,
     ) -> Self {
         let next_vector_id = index.vector_store_info.next_vector_id;
         Self {
             sstable_store: sstable_store.clone(),
+            metrics,
+            index_id,
             committed_vector_files: index.vector_store_info.vector_files.clone(),
             committed_next_vector_id: next_vector_id,
             sealed_vector_files: vec![],
@@ -81,6 +91,9 @@ impl HnswVectorStore {
             self.sstable_store
                 .insert_vector_cache(vector_file.object_id, meta, blocks);
             let file_size = vector_file.file_size as usize;
+            self.metrics
+                .flush_bytes_histogram(&self.index_id)
+                .observe(file_size as f64);
             self.flushed_vector_files.push(vector_file);
             self.flushed_next_vector_id = self.building_vectors.next_vector_id();
             Ok(file_size)
@@ -99,15 +112,36 @@ impl VectorStore for HnswVectorStore {
     async fn get_vector(&self, idx: usize) -> HummockResult<Self::Accessor<'_>> {
         if idx < self.committed_next_vector_id {
             Ok(EnumVectorAccessor::BloclHolder(
-                get_vector_block(&self.sstable_store, &self.committed_vector_files, idx).await?,
+                get_vector_block(
+                    &self.sstable_store,
+                    &self.committed_vector_files,
+                    idx,
+                    &self.metrics,
+                    &self.index_id,
+                )
+                .await?,
             ))
         } else if idx < self.sealed_next_vector_id {
             Ok(EnumVectorAccessor::BloclHolder(
-                get_vector_block(&self.sstable_store, &self.sealed_vector_files, idx).await?,
+                get_vector_block(
+                    &self.sstable_store,
+                    &self.sealed_vector_files,
+                    idx,
+                    &self.metrics,
+                    &self.index_id,
+                )
+                .await?,
             ))
         } else if idx < self.flushed_next_vector_id {
             Ok(EnumVectorAccessor::BloclHolder(
-                get_vector_block(&self.sstable_store, &self.flushed_vector_files, idx).await?,
+                get_vector_block(
+                    &self.sstable_store,
+                    &self.flushed_vector_files,
+                    idx,
+                    &self.metrics,
+                    &self.index_id,
+                )
+                .await?,
             ))
         } else if idx < self.building_vectors.next_vector_id() {
             self.building_vectors.get_vector(idx)
@@ -121,20 +155,59 @@ impl VectorStore for HnswVectorStore {
     }
 }
 
+/// Wraps [`HnswVectorStore`] to count `get_vector` calls made while `insert_graph` performs its
+/// greedy descent. Each candidate visited during the descent costs one distance computation
+/// against the query vector, so this count is a direct observation of the `ef_construction`
+/// distance-computation cost for that insert, rather than a fixed constant.
+///
+/// Tied to the concrete `HnswVectorStore`, not generic over `VectorStore`, matching how
+/// [`crate::hummock::vector::compaction::BuildingVectorStore`] wraps its own concrete inner type
+/// rather than being written generically for testability.
+struct CountingVectorStore<'a> {
+    inner: &'a HnswVectorStore,
+    count: Cell<u64>,
+}
+
+impl VectorStore for CountingVectorStore<'_> {
+    type Accessor<'b>
+        = EnumVectorAccessor<'b>
+    where
+        Self: 'b;
+
+    async fn get_vector(&self, idx: usize) -> HummockResult<Self::Accessor<'_>> {
+        self.count.set(self.count.get() + 1);
+        self.inner.get_vector(idx).await
+    }
+}
+
 pub(crate) struct HnswFlatIndexWriter {
     measure: DistanceMeasurement,
     options: HnswBuilderOptions,
+    dimension: usize,
     sstable_store: SstableStoreRef,
     object_id_manager: ObjectIdManagerRef,
+    storage_opts: StorageOpts,
+    metrics: VectorIndexMetrics,
+    index_id: String,
 
     vector_store: HnswVectorStore,
     next_pending_vector_id: usize,
     graph_builder: Option<HnswGraphBuilder>,
     flushed_graph_file: Option<HnswGraphFileInfo>,
+    /// Tombstoned vector ids, persisted alongside the graph. A deleted node stays reachable
+    /// as a routing hop for the greedy descent in `insert_graph`/query traversal, but is
+    /// filtered out of any candidate set returned to the caller.
+    deleted: RoaringBitmap,
+    /// Tombstones added since the last `seal_current_epoch`, flushed into the next
+    /// `VectorStoreInfoDelta` and reset afterwards.
+    pending_deleted: RoaringBitmap,
+    compaction_trigger: CompactionTriggerPolicy,
     rng: StdRng,
 }
 
 impl HnswFlatIndexWriter {
+    // `metrics`/`index_id` are new params; any caller outside this checkout's query path must be
+    // updated to pass them through.
     pub(crate) async fn new(
         index: &HnswFlatIndex,
         dimension: usize,
@@ -142,6 +215,8 @@ impl HnswFlatIndexWriter {
         sstable_store: SstableStoreRef,
         object_id_manager: ObjectIdManagerRef,
         storage_opts: &StorageOpts,
+        metrics: VectorIndexMetrics,
+        index_id: String,
     ) -> HummockResult<Self> {
         let graph_builder = if let Some(graph_file) = &index.graph_file {
             Some(HnswGraphBuilder::from_protobuf(
@@ -163,11 +238,20 @@ impl HnswFlatIndexWriter {
                 sstable_store.clone(),
                 object_id_manager.clone(),
                 storage_opts,
+                metrics.clone(),
+                index_id.clone(),
             ),
+            dimension,
             sstable_store,
             object_id_manager,
+            storage_opts: storage_opts.clone(),
+            metrics,
+            index_id,
             graph_builder,
             flushed_graph_file: None,
+            deleted: index.vector_store_info.deleted_vector_ids.clone(),
+            pending_deleted: RoaringBitmap::new(),
+            compaction_trigger: CompactionTriggerPolicy::default(),
             rng: StdRng::from_os_rng(),
             next_pending_vector_id: index.vector_store_info.next_vector_id,
         })
@@ -175,9 +259,131 @@ impl HnswFlatIndexWriter {
 
     pub(crate) fn insert(&mut self, vec: Vector, info: Bytes) -> HummockResult<()> {
         self.vector_store.building_vectors.add(vec.to_ref(), &info);
+        self.metrics.insert_counter(&self.index_id).inc();
         Ok(())
     }
 
+    /// Tombstones `vector_id`. The vector stays in the graph so existing edges can still be
+    /// traversed during routing; callers on the query path are expected to run their result set
+    /// through [`HnswFlatIndexWriter::filter_live_candidates`] (or check individual ids via
+    /// [`HnswFlatIndexWriter::is_deleted`]) before returning it.
+    pub(crate) fn delete(&mut self, vector_id: usize) -> HummockResult<()> {
+        if !self.deleted.insert(vector_id as u32) {
+            // already tombstoned
+            return Ok(());
+        }
+        // TODO(#vector-index-entry-point-promotion): if `vector_id` is the graph's current entry
+        // point, promote the highest-level surviving live node in its place instead of leaving a
+        // tombstoned entry point as a permanent routing hop. Blocked on `HnswGraphBuilder`
+        // exposing per-node level/entry-point accessors; `compact` already reclaims this by
+        // rebuilding the graph from live vectors, so descent correctness isn't at risk, but
+        // segments that compact rarely will route through dead entry points longer than
+        // necessary until this lands.
+        self.pending_deleted.insert(vector_id as u32);
+        Ok(())
+    }
+
+    /// Whether `vector_id` has been tombstoned.
+    pub(crate) fn is_deleted(&self, vector_id: usize) -> bool {
+        self.deleted.contains(vector_id as u32)
+    }
+
+    /// Batch form of [`Self::is_deleted`]: drops tombstoned ids from a candidate set, e.g. a
+    /// query's result heap after greedy descent has finished ranking candidates.
+    pub(crate) fn filter_live_candidates(
+        &self,
+        candidates: Vec<(usize, VectorDistance)>,
+    ) -> Vec<(usize, VectorDistance)> {
+        filter_tombstoned(candidates, &self.deleted)
+    }
+
+    /// Returns whether this segment's vector files or tombstoned fraction have crossed the
+    /// configured [`CompactionTriggerPolicy`], making it eligible for the HNSW segment
+    /// compaction that merges vector files and rebuilds the graph over live vectors.
+    pub(crate) fn needs_compaction(&self) -> bool {
+        let total_vectors = self.vector_store.building_vectors.next_vector_id();
+        self.compaction_trigger.should_compact(
+            &self.vector_store.committed_vector_files,
+            &self.deleted,
+            total_vectors,
+        )
+    }
+
+    /// Compacts the committed vector files and graph into a single generation, dropping
+    /// tombstoned vectors and clearing their entries from `deleted` now that they are gone
+    /// from the rebuilt graph entirely. Rolls `committed_vector_files`/`committed_next_vector_id`
+    /// and `graph_builder` forward to the compacted generation, mirroring how
+    /// `seal_current_epoch` rolls its own state forward after producing a delta.
+    ///
+    /// Requires no in-flight sealed/flushed/building generation, since compaction only ever
+    /// rebuilds the already-committed files and remaps their vector ids; a pending generation
+    /// layered on top would otherwise be left referencing stale, pre-remap ids.
+    pub(crate) async fn compact(&mut self) -> HummockResult<VectorIndexAdd> {
+        assert!(self.vector_store.sealed_vector_files.is_empty());
+        assert!(self.vector_store.flushed_vector_files.is_empty());
+        assert!(self.vector_store.building_vectors.is_empty());
+
+        let total_vectors = self.vector_store.committed_next_vector_id;
+        let live_vectors = total_vectors - self.deleted.len() as usize;
+        let add = compact_hnsw_segment(
+            &self.measure,
+            &self.options,
+            self.dimension,
+            total_vectors,
+            &self.vector_store.committed_vector_files,
+            &self.deleted,
+            self.sstable_store.clone(),
+            self.object_id_manager.clone(),
+            &self.storage_opts,
+            self.metrics.clone(),
+            self.index_id.clone(),
+        )
+        .await?;
+        let VectorIndexAdd::HnswFlatCompact(compact) = &add else {
+            unreachable!("compact_hnsw_segment only ever returns VectorIndexAdd::HnswFlatCompact")
+        };
+        self.graph_builder = Some(HnswGraphBuilder::from_protobuf(
+            &self.sstable_store.get_hnsw_graph(&compact.graph_file).await?,
+        ));
+        self.vector_store.committed_vector_files = compact.added_vector_files.clone();
+        self.vector_store.committed_next_vector_id = live_vectors;
+        self.vector_store.sealed_next_vector_id = live_vectors;
+        self.vector_store.flushed_next_vector_id = live_vectors;
+        // `building_vectors` is empty (asserted above), so it is safe to recreate it rather than
+        // leave it numbering new inserts from the stale, pre-compaction id space.
+        self.vector_store.building_vectors = new_vector_file_builder(
+            self.dimension,
+            live_vectors,
+            self.sstable_store.clone(),
+            self.object_id_manager.clone(),
+            &self.storage_opts,
+        );
+        self.next_pending_vector_id = live_vectors;
+        self.deleted = RoaringBitmap::new();
+        Ok(add)
+    }
+
+    /// Checks [`Self::needs_compaction`] and, if triggered, compacts the committed generation.
+    /// Call this right after [`Self::seal_current_epoch`] has returned `None` for the epoch
+    /// (i.e. there is nothing freshly flushed left to seal) on a writer that has not sealed any
+    /// files yet; only then does `compact`'s no-pending-generation precondition hold.
+    ///
+    /// Like [`Self::flush`] and [`Self::seal_current_epoch`], this has no caller within this
+    /// checkout: all three are driven by the vector-index writer's epoch-commit loop, which lives
+    /// outside the files present here and is what decides, per epoch, whether to flush, to seal,
+    /// or — once sealing has nothing left to do — to check for compaction. `seal_current_epoch`
+    /// itself can't just call this on its `None` path: it extends `sealed_vector_files` without
+    /// ever clearing it, so after the instance's first seal, `compact`'s
+    /// `sealed_vector_files.is_empty()` precondition would only hold again once the caller
+    /// recreates the writer from freshly committed [`HnswFlatIndex`] state via [`Self::new`].
+    pub(crate) async fn maybe_compact(&mut self) -> HummockResult<Option<VectorIndexAdd>> {
+        if self.needs_compaction() {
+            Ok(Some(self.compact().await?))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub(crate) fn seal_current_epoch(&mut self) -> Option<VectorIndexAdd> {
         assert!(self.vector_store.building_vectors.is_empty());
         if self.vector_store.flushed_vector_files.is_empty() {
@@ -197,6 +403,7 @@ impl HnswFlatIndexWriter {
             vector_store_info_delta: VectorStoreInfoDelta {
                 next_vector_id: self.vector_store.building_vectors.next_vector_id(),
                 added_vector_files: flushed_vector_files,
+                deleted_vector_ids: take(&mut self.pending_deleted),
             },
             graph_file: new_graph_info,
         }))
@@ -215,6 +422,9 @@ impl HnswFlatIndexWriter {
             PbHnswGraph::encode(&pb_graph, &mut buffer).unwrap();
             let encoded_graph = buffer.freeze();
             let size = encoded_graph.len();
+            self.metrics
+                .encoded_graph_size_histogram(&self.index_id)
+                .observe(size as f64);
             let object_id = self.object_id_manager.get_new_object_id().await?;
             let path = self
                 .sstable_store
@@ -240,9 +450,14 @@ impl HnswFlatIndexWriter {
         for i in self.next_pending_vector_id..self.vector_store.building_vectors.next_vector_id() {
             let node = new_node(&self.options, &mut self.rng);
             if let Some(graph_builder) = &mut self.graph_builder {
+                let _timer = self.metrics.graph_insert_timer(&self.index_id);
+                let counting_store = CountingVectorStore {
+                    inner: &self.vector_store,
+                    count: Cell::new(0),
+                };
                 dispatch_measurement!(&self.measure, M, {
                     insert_graph::<M>(
-                        &self.vector_store,
+                        &counting_store,
                         graph_builder,
                         node,
                         self.vector_store.building_vectors.get_vector(i)?.vec_ref(),
@@ -250,6 +465,10 @@ impl HnswFlatIndexWriter {
                     )
                     .await?;
                 });
+                self.metrics.record_ef_construction_distance_count(
+                    &self.index_id,
+                    counting_store.count.get(),
+                );
             } else {
                 self.graph_builder = Some(HnswGraphBuilder::first(node));
             }
@@ -258,3 +477,28 @@ impl HnswFlatIndexWriter {
         Ok(())
     }
 }
+
+fn filter_tombstoned(
+    candidates: Vec<(usize, VectorDistance)>,
+    deleted: &RoaringBitmap,
+) -> Vec<(usize, VectorDistance)> {
+    candidates
+        .into_iter()
+        .filter(|(vector_id, _)| !deleted.contains(*vector_id as u32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_tombstoned_drops_deleted_ids_only() {
+        let mut deleted = RoaringBitmap::new();
+        deleted.insert(1);
+        deleted.insert(3);
+        let candidates = vec![(0, 0.1), (1, 0.2), (2, 0.3), (3, 0.4)];
+        let live = filter_tombstoned(candidates, &deleted);
+        assert_eq!(live, vec![(0, 0.1), (2, 0.3)]);
+    }
+}
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::simd::Simd;
-use std::simd::num::SimdFloat;
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
 
 use risingwave_pb::hummock::PbDistanceType;
 
@@ -86,82 +88,339 @@ macro_rules! dispatch_measurement {
     };
 }
 
+/// A distance kernel resolved once per query vector by [`pick_kernel`], rather than re-resolved
+/// on every [`MeasureDistance::measure`] call.
+type DistanceKernel = for<'a> fn(VectorRef<'a>, VectorRef<'a>) -> VectorDistance;
+
+/// Like [`DistanceKernel`], but also takes the query vector's magnitude, precomputed once in
+/// [`MeasureDistanceBuilder::new`] and reused across every candidate, instead of being
+/// recomputed from the query on every call.
+type CosineDistanceKernel =
+    for<'a> fn(VectorRef<'a>, VectorRef<'a>, VectorDistance) -> VectorDistance;
+
+/// One candidate's score in a [`MeasureDistanceBatchExt::measure_batch`] max-heap, ordered by
+/// `distance` so the heap's peek/pop is always the current worst of the `k` kept so far.
+struct BatchHeapEntry {
+    distance: VectorDistance,
+    index: usize,
+}
+
+impl PartialEq for BatchHeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl Eq for BatchHeapEntry {}
+
+impl PartialOrd for BatchHeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BatchHeapEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+/// Scores a candidate list against one [`MeasureDistance`] in a single call, so the vector
+/// index doesn't have to loop over `measure` itself. A blanket impl over every `MeasureDistance`,
+/// reachable by generic callers (e.g. `insert_graph<M: MeasureDistanceBuilder>`) simply by
+/// bringing this trait into scope.
+pub trait MeasureDistanceBatchExt: MeasureDistance {
+    /// Scores every candidate with a single pairwise `measure` call while maintaining a bounded
+    /// max-heap of the `k` best (smallest-distance) results seen so far, popping the current
+    /// worst once the heap grows past `k`. Returns the survivors sorted ascending by distance.
+    fn measure_batch(&self, others: &[VectorRef<'_>], k: usize) -> Vec<(usize, VectorDistance)> {
+        let mut heap = std::collections::BinaryHeap::with_capacity(k + 1);
+        for (index, other) in others.iter().enumerate() {
+            let distance = self.measure(*other);
+            heap.push(BatchHeapEntry { distance, index });
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|entry| (entry.index, entry.distance))
+            .collect()
+    }
+}
+
+impl<T: MeasureDistance + ?Sized> MeasureDistanceBatchExt for T {}
+
+/// Whether the host CPU supports the target features the `_simd` kernels in this module were
+/// compiled with. `pick_kernel` falls back to the scalar kernel everywhere else.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn simd_supported() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        is_x86_feature_detected!("avx2")
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        std::arch::is_aarch64_feature_detected!("neon")
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        false
+    }
+}
+
+/// Picks `simd` when built for an architecture the AVX2/NEON `_simd` kernels target *and* the host
+/// CPU supports it at runtime, otherwise falls back to `scalar`, which is always compilable.
+fn pick_kernel<K>(
+    scalar: K,
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))] simd: K,
+) -> K {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if simd_supported() {
+        return simd;
+    }
+    scalar
+}
+
 pub struct L1Distance;
 
-pub struct L1DistanceMeasure<'a>(VectorRef<'a>);
+pub struct L1DistanceMeasure<'a> {
+    target: VectorRef<'a>,
+    kernel: DistanceKernel,
+}
 
 impl MeasureDistanceBuilder for L1Distance {
     type Measure<'a> = L1DistanceMeasure<'a>;
 
     fn new(target: VectorRef<'_>) -> Self::Measure<'_> {
-        L1DistanceMeasure(target)
+        L1DistanceMeasure {
+            target,
+            kernel: pick_kernel(l1_scalar, #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))] l1_simd),
+        }
     }
 }
 
 impl<'a> MeasureDistance for L1DistanceMeasure<'a> {
     fn measure(&self, other: VectorRef<'_>) -> VectorDistance {
-        // TODO: use some library with simd support
-        let len = self.0.0.len();
-        assert_eq!(len, other.0.len());
-        // In this implementation, we don't take the square root to avoid unnecessary computation, because
-        // we only want comparison rather than the actual distance.
-        (0..len)
-            .map(|i| {
-                let diff = self.0.0[i] - other.0[i];
-                diff.abs()
-            })
-            .sum()
+        (self.kernel)(self.target, other)
+    }
+}
+
+// In this implementation, we don't take the square root to avoid unnecessary computation, because
+// we only want comparison rather than the actual distance.
+fn l1_scalar(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorDistance {
+    let len = first.0.len();
+    assert_eq!(len, second.0.len());
+    (0..len)
+        .map(|i| (first.0[i] - second.0[i]).abs())
+        .sum()
+}
+
+/// Safety: outside of tests, only reached as a [`pick_kernel`]-selected kernel, for which
+/// `simd_supported` has already confirmed the running CPU has the enabled target feature; the
+/// tests call it directly.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn l1_simd(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorDistance {
+    let len = first.0.len();
+    assert_eq!(len, second.0.len());
+    unsafe { l1_simd_inner(first.0, second.0, len) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn l1_simd_inner(first: &[VectorItem], second: &[VectorItem], len: usize) -> VectorDistance {
+    let sign_mask = _mm256_set1_ps(-0.0);
+    let mut acc = _mm256_setzero_ps();
+    let mut start = 0;
+    while start + 8 <= len {
+        let a = _mm256_loadu_ps(first.as_ptr().add(start));
+        let b = _mm256_loadu_ps(second.as_ptr().add(start));
+        acc = _mm256_add_ps(acc, _mm256_andnot_ps(sign_mask, _mm256_sub_ps(a, b)));
+        start += 8;
     }
+    let mut lanes = [0.0; 8];
+    _mm256_storeu_ps(lanes.as_mut_ptr(), acc);
+    let tail: VectorDistance = (start..len).map(|i| (first[i] - second[i]).abs()).sum();
+    lanes.iter().sum::<VectorDistance>() + tail
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn l1_simd_inner(first: &[VectorItem], second: &[VectorItem], len: usize) -> VectorDistance {
+    let mut acc = vdupq_n_f32(0.0);
+    let mut start = 0;
+    while start + 4 <= len {
+        let a = vld1q_f32(first.as_ptr().add(start));
+        let b = vld1q_f32(second.as_ptr().add(start));
+        acc = vaddq_f32(acc, vabsq_f32(vsubq_f32(a, b)));
+        start += 4;
+    }
+    let tail: VectorDistance = (start..len).map(|i| (first[i] - second[i]).abs()).sum();
+    vaddvq_f32(acc) + tail
 }
 
 pub struct L2Distance;
 
-pub struct L2DistanceMeasure<'a>(VectorRef<'a>);
+pub struct L2DistanceMeasure<'a> {
+    target: VectorRef<'a>,
+    kernel: DistanceKernel,
+}
 
 impl MeasureDistanceBuilder for L2Distance {
     type Measure<'a> = L2DistanceMeasure<'a>;
 
     fn new(target: VectorRef<'_>) -> Self::Measure<'_> {
-        L2DistanceMeasure(target)
+        L2DistanceMeasure {
+            target,
+            kernel: pick_kernel(l2_scalar, #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))] l2_simd),
+        }
     }
 }
 
 impl<'a> MeasureDistance for L2DistanceMeasure<'a> {
     fn measure(&self, other: VectorRef<'_>) -> VectorDistance {
-        // TODO: use some library with simd support
-        let len = self.0.0.len();
-        assert_eq!(len, other.0.len());
-        // In this implementation, we don't take the square root to avoid unnecessary computation, because
-        // we only want comparison rather than the actual distance.
-        (0..len).map(|i| (self.0.0[i] - other.0[i]).powi(2)).sum()
+        (self.kernel)(self.target, other)
+    }
+}
+
+// In this implementation, we don't take the square root to avoid unnecessary computation, because
+// we only want comparison rather than the actual distance.
+fn l2_scalar(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorDistance {
+    let len = first.0.len();
+    assert_eq!(len, second.0.len());
+    (0..len).map(|i| (first.0[i] - second.0[i]).powi(2)).sum()
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn l2_simd(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorDistance {
+    let len = first.0.len();
+    assert_eq!(len, second.0.len());
+    unsafe { l2_simd_inner(first.0, second.0, len) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn l2_simd_inner(first: &[VectorItem], second: &[VectorItem], len: usize) -> VectorDistance {
+    let mut acc = _mm256_setzero_ps();
+    let mut start = 0;
+    while start + 8 <= len {
+        let a = _mm256_loadu_ps(first.as_ptr().add(start));
+        let b = _mm256_loadu_ps(second.as_ptr().add(start));
+        let diff = _mm256_sub_ps(a, b);
+        acc = _mm256_add_ps(acc, _mm256_mul_ps(diff, diff));
+        start += 8;
+    }
+    let mut lanes = [0.0; 8];
+    _mm256_storeu_ps(lanes.as_mut_ptr(), acc);
+    let tail: VectorDistance = (start..len).map(|i| (first[i] - second[i]).powi(2)).sum();
+    lanes.iter().sum::<VectorDistance>() + tail
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn l2_simd_inner(first: &[VectorItem], second: &[VectorItem], len: usize) -> VectorDistance {
+    let mut acc = vdupq_n_f32(0.0);
+    let mut start = 0;
+    while start + 4 <= len {
+        let a = vld1q_f32(first.as_ptr().add(start));
+        let b = vld1q_f32(second.as_ptr().add(start));
+        let diff = vsubq_f32(a, b);
+        acc = vfmaq_f32(acc, diff, diff);
+        start += 4;
     }
+    let tail: VectorDistance = (start..len).map(|i| (first[i] - second[i]).powi(2)).sum();
+    vaddvq_f32(acc) + tail
 }
 
 pub struct CosineDistance;
 pub struct CosineDistanceMeasure<'a> {
     target: VectorRef<'a>,
-    magnitude: VectorItem,
+    /// The query vector's magnitude, computed once here rather than on every `measure` call.
+    target_magnitude: VectorDistance,
+    kernel: CosineDistanceKernel,
 }
 
 impl MeasureDistanceBuilder for CosineDistance {
     type Measure<'a> = CosineDistanceMeasure<'a>;
 
     fn new(target: VectorRef<'_>) -> Self::Measure<'_> {
-        let magnitude = target.magnitude();
-        CosineDistanceMeasure { target, magnitude }
+        CosineDistanceMeasure {
+            target,
+            target_magnitude: target.magnitude(),
+            kernel: pick_kernel(cosine_scalar, #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))] cosine_simd),
+        }
     }
 }
 
 impl<'a> MeasureDistance for CosineDistanceMeasure<'a> {
     fn measure(&self, other: VectorRef<'_>) -> VectorDistance {
-        // TODO: use some library with simd support
-        let len = self.target.0.len();
-        assert_eq!(len, other.0.len());
-        let magnitude_mul = other.magnitude() * self.magnitude;
-        1.0 - (0..len)
-            .map(|i| self.target.0[i] * other.0[i] / magnitude_mul)
-            .sum::<VectorDistance>()
+        (self.kernel)(self.target, other, self.target_magnitude)
+    }
+}
+
+fn cosine_scalar(
+    first: VectorRef<'_>,
+    second: VectorRef<'_>,
+    first_magnitude: VectorDistance,
+) -> VectorDistance {
+    let len = first.0.len();
+    assert_eq!(len, second.0.len());
+    let magnitude_mul = first_magnitude * second.magnitude();
+    1.0 - (0..len)
+        .map(|i| first.0[i] * second.0[i] / magnitude_mul)
+        .sum::<VectorDistance>()
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn cosine_simd(
+    first: VectorRef<'_>,
+    second: VectorRef<'_>,
+    first_magnitude: VectorDistance,
+) -> VectorDistance {
+    let len = first.0.len();
+    assert_eq!(len, second.0.len());
+    let dot = unsafe { dot_product_simd_inner(first.0, second.0, len) };
+    1.0 - dot / (first_magnitude * second.magnitude())
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn dot_product_simd_inner(
+    first: &[VectorItem],
+    second: &[VectorItem],
+    len: usize,
+) -> VectorDistance {
+    let mut acc = _mm256_setzero_ps();
+    let mut start = 0;
+    while start + 8 <= len {
+        let a = _mm256_loadu_ps(first.as_ptr().add(start));
+        let b = _mm256_loadu_ps(second.as_ptr().add(start));
+        acc = _mm256_add_ps(acc, _mm256_mul_ps(a, b));
+        start += 8;
+    }
+    let mut lanes = [0.0; 8];
+    _mm256_storeu_ps(lanes.as_mut_ptr(), acc);
+    let tail: VectorDistance = (start..len).map(|i| first[i] * second[i]).sum();
+    lanes.iter().sum::<VectorDistance>() + tail
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn dot_product_simd_inner(
+    first: &[VectorItem],
+    second: &[VectorItem],
+    len: usize,
+) -> VectorDistance {
+    let mut acc = vdupq_n_f32(0.0);
+    let mut start = 0;
+    while start + 4 <= len {
+        let a = vld1q_f32(first.as_ptr().add(start));
+        let b = vld1q_f32(second.as_ptr().add(start));
+        acc = vfmaq_f32(acc, a, b);
+        start += 4;
     }
+    let tail: VectorDistance = (start..len).map(|i| first[i] * second[i]).sum();
+    vaddvq_f32(acc) + tail
 }
 
 pub struct InnerProductDistance;
@@ -184,24 +443,12 @@ fn inner_product_trivial(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorD
         .sum::<VectorItem>()
 }
 
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 #[cfg_attr(not(test), expect(dead_code))]
 fn inner_product_simd(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorDistance {
     let len = first.0.len();
     assert_eq!(len, second.0.len());
-    let mut sum = 0.0;
-    let mut start = 0;
-    let mut end = start + 32;
-    while end <= len {
-        let this = Simd::<VectorItem, 32>::from_slice(&first.0[start..end]);
-        let target = Simd::<VectorItem, 32>::from_slice(&second.0[start..end]);
-        sum += (this * target).reduce_sum();
-        start += 32;
-        end += 32;
-    }
-    -((start..len)
-        .map(|i| first.0[i] * second.0[i])
-        .sum::<VectorDistance>()
-        + sum)
+    -unsafe { dot_product_simd_inner(first.0, second.0, len) }
 }
 
 fn inner_product_faiss(first: VectorRef<'_>, second: VectorRef<'_>) -> VectorDistance {
@@ -214,6 +461,35 @@ impl<'a> MeasureDistance for InnerProductDistanceMeasure<'a> {
     }
 }
 
+impl<'a> InnerProductDistanceMeasure<'a> {
+    /// Inherent counterpart of [`MeasureDistanceBatchExt::measure_batch`], taken by direct
+    /// (non-generic) callers in its place; it calls `inner_product_faiss` straight from the loop
+    /// that maintains the bounded max-heap of the `k` best candidates and returns the same result.
+    ///
+    /// TODO(vector-index): this still costs one FFI crossing per candidate, which dominates for
+    /// large candidate sets; switch the loop below to a single `fvec_inner_products_ny`-style
+    /// batched faiss primitive once the vendored `faiss::utils` bindings expose one (today they
+    /// only expose the pairwise `fvec_inner_product`).
+    pub(crate) fn measure_batch(
+        &self,
+        others: &[VectorRef<'_>],
+        k: usize,
+    ) -> Vec<(usize, VectorDistance)> {
+        let mut heap = std::collections::BinaryHeap::with_capacity(k + 1);
+        for (index, other) in others.iter().enumerate() {
+            let distance = inner_product_faiss(self.0, *other);
+            heap.push(BatchHeapEntry { distance, index });
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|entry| (entry.index, entry.distance))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -284,9 +560,27 @@ mod tests {
             let v1 = gen_vector(128);
             let v2 = gen_vector(128);
             let trivial = inner_product_trivial(v1.to_ref(), v2.to_ref());
+            #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
             assert_eq_float!(inner_product_simd(v1.to_ref(), v2.to_ref()), trivial);
             assert_eq_float!(inner_product_faiss(v1.to_ref(), v2.to_ref()), trivial);
         }
+        #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+        {
+            let v1 = gen_vector(128);
+            let v2 = gen_vector(128);
+            assert_eq_float!(
+                l1_simd(v1.to_ref(), v2.to_ref()),
+                L1Distance::distance(v1.to_ref(), v2.to_ref())
+            );
+            assert_eq_float!(
+                l2_simd(v1.to_ref(), v2.to_ref()),
+                L2Distance::distance(v1.to_ref(), v2.to_ref())
+            );
+            assert_eq_float!(
+                cosine_simd(v1.to_ref(), v2.to_ref(), v1.to_ref().magnitude()),
+                CosineDistance::distance(v1.to_ref(), v2.to_ref())
+            );
+        }
     }
 
     #[test]
@@ -320,4 +614,72 @@ mod tests {
             VectorInner(&VEC2),
         ));
     }
+
+    #[test]
+    fn test_measure_batch() {
+        let target = gen_vector(16);
+        let candidates: Vec<_> = (0..20).map(|_| gen_vector(16)).collect();
+        let candidate_refs: Vec<_> = candidates.iter().map(|v| v.to_ref()).collect();
+
+        let measure = L2Distance::new(target.to_ref());
+        let top_k = measure.measure_batch(&candidate_refs, 5);
+        assert_eq!(top_k.len(), 5);
+
+        // Results are sorted ascending by distance.
+        for pair in top_k.windows(2) {
+            assert!(pair[0].1 <= pair[1].1);
+        }
+
+        // The smallest distance returned must really be the smallest among all candidates.
+        let all_distances: Vec<_> = candidate_refs
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, measure.measure(*c)))
+            .collect();
+        let min_distance = all_distances
+            .iter()
+            .map(|(_, d)| *d)
+            .fold(VectorDistance::INFINITY, f32::min);
+        assert_eq_float!(top_k[0].1, min_distance);
+
+        // Requesting more than the candidate set just returns every candidate.
+        let all_top_k = measure.measure_batch(&candidate_refs, candidate_refs.len() + 5);
+        assert_eq!(all_top_k.len(), candidate_refs.len());
+    }
+
+    /// `measure_batch` being a trait-default method (not an inherent one on each concrete
+    /// `*Measure` struct) is what lets generic candidate-scoring code reach it through nothing
+    /// more than a `MeasureDistance` bound, the way `insert_graph<M: MeasureDistanceBuilder>` knows
+    /// its `M::Measure<'_>` only through that bound.
+    fn top_k_via_generic_bound<M: MeasureDistance>(
+        measure: &M,
+        candidates: &[VectorRef<'_>],
+        k: usize,
+    ) -> Vec<(usize, VectorDistance)> {
+        measure.measure_batch(candidates, k)
+    }
+
+    #[test]
+    fn test_measure_batch_reachable_through_generic_bound() {
+        let target = gen_vector(16);
+        let candidates: Vec<_> = (0..20).map(|_| gen_vector(16)).collect();
+        let candidate_refs: Vec<_> = candidates.iter().map(|v| v.to_ref()).collect();
+        let measure = CosineDistance::new(target.to_ref());
+        let top_k = top_k_via_generic_bound(&measure, &candidate_refs, 5);
+        assert_eq!(top_k.len(), 5);
+    }
+
+    /// `cosine_scalar`/`cosine_simd` take the query's magnitude as a parameter instead of
+    /// recomputing `first.magnitude()` themselves, so that `CosineDistanceMeasure::new` can cache
+    /// it once and every `measure()` call reuses it. If `cosine_scalar` ignored the parameter and
+    /// recomputed the magnitude internally, passing a wrong value here would have no effect; this
+    /// asserts it does.
+    #[test]
+    fn test_cosine_scalar_uses_passed_in_magnitude() {
+        let v1 = VectorInner(&VEC1);
+        let v2 = VectorInner(&VEC2);
+        let correct = cosine_scalar(v1, v2, v1.magnitude());
+        let wrong = cosine_scalar(v1, v2, v1.magnitude() * 2.0);
+        assert_ne!(correct, wrong);
+    }
 }